@@ -0,0 +1,235 @@
+//! [`GitBackend`] implementation that shells out to the system `git` CLI,
+//! for hosts where linking libgit2 is a problem.
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+use super::{BackendError, BackendResult, GitBackend, NOTES_REF};
+
+/// Backend that drives `git` as a subprocess instead of linking libgit2.
+#[derive(Default)]
+pub struct CliBackend;
+
+impl CliBackend {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn git(&self, args: &[&str]) -> BackendResult<Output> {
+        Command::new("git")
+            .args(args)
+            .output()
+            .map_err(|e| BackendError::Other(anyhow::anyhow!(e)))
+    }
+
+    fn run_ok(&self, args: &[&str]) -> BackendResult<String> {
+        let output = self.git(args)?;
+        classify(&output)?;
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+}
+
+/// Map a non-zero git exit to a typed [`BackendError`] based on stderr,
+/// since the `git` CLI doesn't give us more than an exit code and text.
+fn classify(output: &Output) -> BackendResult<()> {
+    if output.status.success() {
+        return Ok(());
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    if stderr.contains("not a git repository") {
+        return Err(BackendError::NotARepo(stderr.trim().to_string()));
+    }
+
+    if stderr.to_lowercase().contains("no note found") {
+        return Err(BackendError::NoteNotFound(stderr.trim().to_string()));
+    }
+
+    if stderr.contains("Permission denied") {
+        return Err(BackendError::PermissionDenied(stderr.trim().to_string()));
+    }
+
+    Err(BackendError::Other(anyhow::anyhow!(
+        stderr.trim().to_string()
+    )))
+}
+
+impl GitBackend for CliBackend {
+    fn discover_workdir(&self, start_path: &Path) -> BackendResult<PathBuf> {
+        let output = Command::new("git")
+            .args(["rev-parse", "--show-toplevel"])
+            .current_dir(start_path)
+            .output()
+            .map_err(|e| BackendError::Other(anyhow::anyhow!(e)))?;
+
+        classify(&output)?;
+        Ok(PathBuf::from(
+            String::from_utf8_lossy(&output.stdout).trim(),
+        ))
+    }
+
+    fn revparse_commit(&self, rev: &str) -> BackendResult<String> {
+        self.run_ok(&["rev-parse", &format!("{}^{{commit}}", rev)])
+    }
+
+    fn commit_time(&self, commit_oid: &str) -> BackendResult<i64> {
+        let output = self.run_ok(&["show", "-s", "--format=%ct", commit_oid])?;
+        output
+            .trim()
+            .parse::<i64>()
+            .map_err(|e| BackendError::Other(anyhow::anyhow!(e)))
+    }
+
+    fn commits_between(&self, base: &str, head: &str) -> BackendResult<Vec<String>> {
+        let output = self.run_ok(&[
+            "rev-list",
+            "--reverse",
+            &format!("{}..{}", base, head),
+        ])?;
+
+        Ok(output
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .map(str::to_string)
+            .collect())
+    }
+
+    fn blame_lines(&self, path: &Path) -> BackendResult<Vec<String>> {
+        // Anchor to the file's own directory rather than the process's cwd —
+        // a long-running caller like the LSP server can't rely on its cwd
+        // being inside the repo at all, let alone being the workspace root
+        // `git blame` would otherwise resolve `path` against.
+        let dir = path.parent().filter(|p| !p.as_os_str().is_empty());
+        let file_name = path.file_name().map(|f| f.to_string_lossy().into_owned());
+        let (dir, file_arg) = match (dir, file_name) {
+            (Some(dir), Some(file_name)) => (dir, file_name),
+            _ => (Path::new("."), path.to_string_lossy().into_owned()),
+        };
+
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["blame", "--porcelain", "--", &file_arg])
+            .output()
+            .map_err(|e| BackendError::Other(anyhow::anyhow!(e)))?;
+        classify(&output)?;
+
+        let text = String::from_utf8_lossy(&output.stdout);
+        let mut lines = Vec::new();
+        let mut current_sha = String::new();
+
+        for line in text.lines() {
+            if let Some(token) = line.split_whitespace().next() {
+                if token.len() == 40 && token.chars().all(|c| c.is_ascii_hexdigit()) {
+                    current_sha = token.to_string();
+                }
+            }
+
+            if line.starts_with('\t') {
+                lines.push(current_sha.clone());
+            }
+        }
+
+        Ok(lines)
+    }
+
+    fn changed_files(&self, commit_oid: &str) -> BackendResult<Vec<String>> {
+        let output = self.run_ok(&["show", "--name-only", "--pretty=format:", commit_oid])?;
+
+        Ok(output
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .map(str::to_string)
+            .collect())
+    }
+
+    fn fetch_notes(&self) -> BackendResult<()> {
+        let refspec = format!("{}:{}", NOTES_REF, NOTES_REF);
+        self.run_ok(&["fetch", "origin", &refspec])?;
+        Ok(())
+    }
+
+    fn push_notes(&self) -> BackendResult<()> {
+        let refspec = format!("{}:{}", NOTES_REF, NOTES_REF);
+        self.run_ok(&["push", "origin", &refspec])?;
+        Ok(())
+    }
+
+    fn read_note(&self, start_path: &Path, commit_oid: &str) -> BackendResult<Option<String>> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(start_path)
+            .args([
+                "notes",
+                &format!("--ref={}", NOTES_REF),
+                "show",
+                commit_oid,
+            ])
+            .output()
+            .map_err(|e| BackendError::Other(anyhow::anyhow!(e)))?;
+
+        if output.status.success() {
+            return Ok(Some(
+                String::from_utf8_lossy(&output.stdout)
+                    .trim_end()
+                    .to_string(),
+            ));
+        }
+
+        match classify(&output) {
+            Err(BackendError::NoteNotFound(_)) => Ok(None),
+            Err(err) => Err(err),
+            Ok(()) => Ok(None),
+        }
+    }
+
+    fn write_note(&self, commit_oid: &str, content: &str) -> BackendResult<()> {
+        self.run_ok(&[
+            "notes",
+            &format!("--ref={}", NOTES_REF),
+            "add",
+            "-f",
+            "-m",
+            content,
+            commit_oid,
+        ])?;
+        Ok(())
+    }
+
+    fn copy_note(&self, source_oid: &str, target_oid: &str) -> BackendResult<()> {
+        self.run_ok(&[
+            "notes",
+            &format!("--ref={}", NOTES_REF),
+            "copy",
+            source_oid,
+            target_oid,
+        ])?;
+        Ok(())
+    }
+
+    fn config_get(&self, key: &str) -> BackendResult<Option<String>> {
+        let output = self.git(&["config", "--get", key])?;
+
+        if output.status.success() {
+            return Ok(Some(
+                String::from_utf8_lossy(&output.stdout).trim().to_string(),
+            ));
+        }
+
+        // `git config --get` exits 1 (not an error) when the key is unset.
+        if output.status.code() == Some(1) {
+            return Ok(None);
+        }
+
+        classify(&output)?;
+        Ok(None)
+    }
+
+    fn config_add(&self, key: &str, value: &str) -> BackendResult<()> {
+        self.run_ok(&["config", "--add", key, value])?;
+        Ok(())
+    }
+}
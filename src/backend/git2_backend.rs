@@ -0,0 +1,252 @@
+//! [`GitBackend`] implementation backed by libgit2 — the original code path.
+
+use std::path::{Path, PathBuf};
+
+use git2::{Cred, ErrorCode, RemoteCallbacks, Repository};
+
+use super::{BackendError, BackendResult, GitBackend, NOTES_REF};
+
+/// Default backend: everything routed through `git2`/libgit2.
+#[derive(Default)]
+pub struct Git2Backend;
+
+impl Git2Backend {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn open(&self, start_path: &Path) -> BackendResult<Repository> {
+        Repository::discover(start_path)
+            .map_err(|e| BackendError::NotARepo(e.message().to_string()))
+    }
+}
+
+impl GitBackend for Git2Backend {
+    fn discover_workdir(&self, start_path: &Path) -> BackendResult<PathBuf> {
+        let repo = self.open(start_path)?;
+        repo.workdir().map(Path::to_path_buf).ok_or_else(|| {
+            BackendError::NotARepo("no working directory (bare repo)".to_string())
+        })
+    }
+
+    fn revparse_commit(&self, rev: &str) -> BackendResult<String> {
+        let repo = self.open(Path::new("."))?;
+        let commit = repo
+            .revparse_single(rev)
+            .and_then(|obj| obj.peel_to_commit())
+            .map_err(|e| BackendError::Other(anyhow::anyhow!(e)))?;
+        Ok(commit.id().to_string())
+    }
+
+    fn commit_time(&self, commit_oid: &str) -> BackendResult<i64> {
+        let repo = self.open(Path::new("."))?;
+        let oid = parse_oid(commit_oid)?;
+        let commit = repo
+            .find_commit(oid)
+            .map_err(|e| BackendError::Other(anyhow::anyhow!(e)))?;
+
+        Ok(commit.time().seconds())
+    }
+
+    fn commits_between(&self, base: &str, head: &str) -> BackendResult<Vec<String>> {
+        let repo = self.open(Path::new("."))?;
+        let base_oid = parse_oid(base)?;
+        let head_oid = parse_oid(head)?;
+
+        let mut revwalk = repo
+            .revwalk()
+            .map_err(|e| BackendError::Other(anyhow::anyhow!(e)))?;
+        revwalk
+            .push(head_oid)
+            .map_err(|e| BackendError::Other(anyhow::anyhow!(e)))?;
+        revwalk
+            .hide(base_oid)
+            .map_err(|e| BackendError::Other(anyhow::anyhow!(e)))?;
+
+        let mut oids = Vec::new();
+        for oid in revwalk {
+            let oid = oid.map_err(|e| BackendError::Other(anyhow::anyhow!(e)))?;
+            oids.push(oid.to_string());
+        }
+        oids.reverse();
+
+        Ok(oids)
+    }
+
+    fn blame_lines(&self, path: &Path) -> BackendResult<Vec<String>> {
+        let repo = self.open(path.parent().unwrap_or(path))?;
+        let workdir = repo.workdir().ok_or_else(|| {
+            BackendError::NotARepo("no working directory (bare repo)".to_string())
+        })?;
+        let relative = path.strip_prefix(workdir).unwrap_or(path);
+
+        let blame = repo
+            .blame_file(relative, None)
+            .map_err(|e| BackendError::Other(anyhow::anyhow!(e)))?;
+
+        let mut lines = Vec::new();
+        for hunk in blame.iter() {
+            let oid = hunk.final_commit_id().to_string();
+            for _ in 0..hunk.lines_in_hunk() {
+                lines.push(oid.clone());
+            }
+        }
+
+        Ok(lines)
+    }
+
+    fn changed_files(&self, commit_oid: &str) -> BackendResult<Vec<String>> {
+        let repo = self.open(Path::new("."))?;
+        let oid = parse_oid(commit_oid)?;
+        let commit = repo
+            .find_commit(oid)
+            .map_err(|e| BackendError::Other(anyhow::anyhow!(e)))?;
+        let tree = commit
+            .tree()
+            .map_err(|e| BackendError::Other(anyhow::anyhow!(e)))?;
+        let parent_tree = commit.parent(0).ok().and_then(|p| p.tree().ok());
+
+        let diff = repo
+            .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)
+            .map_err(|e| BackendError::Other(anyhow::anyhow!(e)))?;
+
+        let mut paths = Vec::new();
+        diff.foreach(
+            &mut |delta, _| {
+                if let Some(path) = delta.new_file().path() {
+                    paths.push(path.to_string_lossy().into_owned());
+                }
+                true
+            },
+            None,
+            None,
+            None,
+        )
+        .map_err(|e| BackendError::Other(anyhow::anyhow!(e)))?;
+
+        Ok(paths)
+    }
+
+    fn fetch_notes(&self) -> BackendResult<()> {
+        let repo = self.open(Path::new("."))?;
+        let mut remote = repo
+            .find_remote("origin")
+            .map_err(|e| BackendError::Other(anyhow::anyhow!(e)))?;
+        let refspec = format!("{}:{}", NOTES_REF, NOTES_REF);
+
+        let mut callbacks = RemoteCallbacks::new();
+        callbacks.credentials(credentials_callback);
+        let mut fetch_options = git2::FetchOptions::new();
+        fetch_options.remote_callbacks(callbacks);
+
+        remote
+            .fetch(&[refspec.as_str()], Some(&mut fetch_options), None)
+            .map_err(|e| BackendError::Other(anyhow::anyhow!(e)))?;
+
+        Ok(())
+    }
+
+    fn push_notes(&self) -> BackendResult<()> {
+        let repo = self.open(Path::new("."))?;
+        let mut remote = repo
+            .find_remote("origin")
+            .map_err(|e| BackendError::Other(anyhow::anyhow!(e)))?;
+        let refspec = format!("{}:{}", NOTES_REF, NOTES_REF);
+
+        let mut callbacks = RemoteCallbacks::new();
+        callbacks.credentials(credentials_callback);
+        let mut push_options = git2::PushOptions::new();
+        push_options.remote_callbacks(callbacks);
+
+        remote
+            .push(&[refspec.as_str()], Some(&mut push_options))
+            .map_err(|e| BackendError::Other(anyhow::anyhow!(e)))?;
+
+        Ok(())
+    }
+
+    fn read_note(&self, start_path: &Path, commit_oid: &str) -> BackendResult<Option<String>> {
+        let repo = self.open(start_path)?;
+        let oid = parse_oid(commit_oid)?;
+
+        match repo.find_note(Some(NOTES_REF), oid) {
+            Ok(note) => Ok(note.message().map(str::to_string)),
+            Err(e) if e.code() == ErrorCode::NotFound => Ok(None),
+            Err(e) => Err(BackendError::Other(anyhow::anyhow!(e))),
+        }
+    }
+
+    fn write_note(&self, commit_oid: &str, content: &str) -> BackendResult<()> {
+        let repo = self.open(Path::new("."))?;
+        let oid = parse_oid(commit_oid)?;
+        let signature = repo
+            .signature()
+            .map_err(|e| BackendError::Other(anyhow::anyhow!(e)))?;
+
+        repo.note(&signature, &signature, Some(NOTES_REF), oid, content, true)
+            .map_err(|e| BackendError::Other(anyhow::anyhow!(e)))?;
+
+        Ok(())
+    }
+
+    fn copy_note(&self, source_oid: &str, target_oid: &str) -> BackendResult<()> {
+        let content = self
+            .read_note(Path::new("."), source_oid)?
+            .ok_or_else(|| BackendError::NoteNotFound(source_oid.to_string()))?;
+
+        self.write_note(target_oid, &content)
+    }
+
+    fn config_get(&self, key: &str) -> BackendResult<Option<String>> {
+        let repo = self.open(Path::new("."))?;
+        let config = repo
+            .config()
+            .map_err(|e| BackendError::Other(anyhow::anyhow!(e)))?;
+
+        match config.get_string(key) {
+            Ok(value) => Ok(Some(value)),
+            Err(e) if e.code() == ErrorCode::NotFound => Ok(None),
+            Err(e) => Err(BackendError::Other(anyhow::anyhow!(e))),
+        }
+    }
+
+    fn config_add(&self, key: &str, value: &str) -> BackendResult<()> {
+        let repo = self.open(Path::new("."))?;
+        let mut config = repo
+            .config()
+            .map_err(|e| BackendError::Other(anyhow::anyhow!(e)))?;
+
+        config
+            .set_multivar(key, "^$", value)
+            .or_else(|_| config.set_str(key, value))
+            .map_err(|e| BackendError::Other(anyhow::anyhow!(e)))?;
+
+        Ok(())
+    }
+}
+
+fn parse_oid(commit_oid: &str) -> BackendResult<git2::Oid> {
+    git2::Oid::from_str(commit_oid).map_err(|e| BackendError::Other(anyhow::anyhow!(e)))
+}
+
+/// Credentials for fetching/pushing `refs/notes/ai-blame` against a real
+/// remote, since libgit2 (unlike the `git` CLI) doesn't transparently pick
+/// up the ssh-agent or a stored HTTPS token on its own. Tries the ssh-agent
+/// first, then falls back to whatever credential helper the user's git
+/// config already has configured for HTTPS remotes.
+fn credentials_callback(
+    url: &str,
+    username_from_url: Option<&str>,
+    allowed_types: git2::CredentialType,
+) -> Result<Cred, git2::Error> {
+    if allowed_types.contains(git2::CredentialType::SSH_KEY) {
+        if let Some(username) = username_from_url {
+            if let Ok(cred) = Cred::ssh_key_from_agent(username) {
+                return Ok(cred);
+            }
+        }
+    }
+
+    let config = git2::Config::open_default()?;
+    Cred::credential_helper(&config, url, username_from_url)
+}
@@ -0,0 +1,148 @@
+//! Pluggable git backend.
+//!
+//! `NotesStore`, the commit-walking and blame logic, and the CLI commands
+//! all go through [`GitBackend`] for every git operation they need:
+//! discovering the repo, resolving and walking revs, reading per-line
+//! blame, reading/writing/copying notes under `refs/notes/ai-blame`,
+//! syncing that ref, and a couple of config operations. Routing all of
+//! that through one trait means the binary can run against either libgit2
+//! ([`Git2Backend`]) or the system `git` CLI ([`CliBackend`]) on hosts
+//! where linking libgit2 is a problem — callers ask [`active`] for
+//! whichever one `--git-backend`/`AI_BLAME_GIT_BACKEND` selected and never
+//! touch `git2::Repository` themselves.
+
+pub mod cli_backend;
+pub mod git2_backend;
+
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use thiserror::Error;
+
+pub use cli_backend::CliBackend;
+pub use git2_backend::Git2Backend;
+
+/// The notes ref ai-blame stores attribution under.
+pub const NOTES_REF: &str = "refs/notes/ai-blame";
+
+/// Errors a [`GitBackend`] implementation can surface, normalized across
+/// the libgit2 and CLI implementations so callers can match on them
+/// without caring which backend produced the failure.
+#[derive(Debug, Error)]
+pub enum BackendError {
+    #[error("not a git repository: {0}")]
+    NotARepo(String),
+
+    #[error("note not found: {0}")]
+    NoteNotFound(String),
+
+    #[error("permission denied: {0}")]
+    PermissionDenied(String),
+
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+pub type BackendResult<T> = Result<T, BackendError>;
+
+/// Which [`GitBackend`] implementation to use, selectable via
+/// `--git-backend`/`AI_BLAME_GIT_BACKEND`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum GitBackendKind {
+    /// Linked libgit2 (default).
+    #[value(name = "git2")]
+    Git2,
+    /// Shell out to the system `git` CLI.
+    #[value(name = "cli")]
+    Cli,
+}
+
+/// Git operations needed by ai-blame's storage and capture layers.
+pub trait GitBackend: Send + Sync {
+    /// Discover the working directory of the repo containing `start_path`.
+    fn discover_workdir(&self, start_path: &Path) -> BackendResult<PathBuf>;
+
+    /// Resolve `rev` to the OID of the commit it points at.
+    fn revparse_commit(&self, rev: &str) -> BackendResult<String>;
+
+    /// The committer time of `commit_oid`, as a Unix timestamp (seconds since
+    /// the epoch) — stamped onto an attribution note so the LSP hover and
+    /// `prompt` can surface when an AI-authored line was committed, not just
+    /// which tool/prompt produced it.
+    fn commit_time(&self, commit_oid: &str) -> BackendResult<i64>;
+
+    /// List commit OIDs reachable from `head` but not from `base`, oldest
+    /// first (equivalent to `git rev-list --reverse base..head`).
+    fn commits_between(&self, base: &str, head: &str) -> BackendResult<Vec<String>>;
+
+    /// Per-line commit OIDs for `path`, in line order — the same data
+    /// `git blame` computes.
+    fn blame_lines(&self, path: &Path) -> BackendResult<Vec<String>>;
+
+    /// Paths touched by `commit_oid`, relative to the repo root — used to
+    /// split a burst of pending captures across the several commits that
+    /// landed between two watch-daemon polls, by matching each capture's
+    /// file against the commit that actually touched it.
+    fn changed_files(&self, commit_oid: &str) -> BackendResult<Vec<String>>;
+
+    /// Fetch `refs/notes/ai-blame` from `origin`.
+    fn fetch_notes(&self) -> BackendResult<()>;
+
+    /// Push `refs/notes/ai-blame` to `origin`.
+    fn push_notes(&self) -> BackendResult<()>;
+
+    /// Read the ai-blame note attached to `commit_oid`, if any, resolving
+    /// the repo from `start_path` rather than assuming the process's
+    /// current directory — long-running callers like the LSP server can't
+    /// rely on their cwd being the workspace root.
+    fn read_note(&self, start_path: &Path, commit_oid: &str) -> BackendResult<Option<String>>;
+
+    /// Write (replacing any existing) the ai-blame note on `commit_oid`.
+    fn write_note(&self, commit_oid: &str, content: &str) -> BackendResult<()>;
+
+    /// Copy the ai-blame note from `source_oid` to `target_oid`.
+    fn copy_note(&self, source_oid: &str, target_oid: &str) -> BackendResult<()>;
+
+    /// Read a single config value.
+    fn config_get(&self, key: &str) -> BackendResult<Option<String>>;
+
+    /// Add `value` to a multivar config entry without replacing existing values.
+    fn config_add(&self, key: &str, value: &str) -> BackendResult<()>;
+}
+
+/// Construct a [`GitBackend`] for `requested`, falling back to the
+/// `AI_BLAME_GIT_BACKEND` env var and then [`GitBackendKind::Git2`]. Errors
+/// rather than silently falling back when the env var holds a value that
+/// isn't a recognized backend name.
+pub fn select(requested: Option<GitBackendKind>) -> anyhow::Result<Box<dyn GitBackend>> {
+    let kind = match requested {
+        Some(kind) => kind,
+        None => match std::env::var("AI_BLAME_GIT_BACKEND") {
+            Ok(value) => clap::ValueEnum::from_str(&value, false).map_err(|_| {
+                anyhow::anyhow!(
+                    "invalid AI_BLAME_GIT_BACKEND {value:?}: expected \"git2\" or \"cli\""
+                )
+            })?,
+            Err(_) => GitBackendKind::Git2,
+        },
+    };
+
+    Ok(match kind {
+        GitBackendKind::Git2 => Box::new(Git2Backend::new()),
+        GitBackendKind::Cli => Box::new(CliBackend::new()),
+    })
+}
+
+static ACTIVE: OnceLock<Box<dyn GitBackend>> = OnceLock::new();
+
+/// Set the process-wide active backend. Intended to be called once at
+/// startup, after resolving `--git-backend` / `AI_BLAME_GIT_BACKEND`.
+pub fn set_active(backend: Box<dyn GitBackend>) {
+    let _ = ACTIVE.set(backend);
+}
+
+/// Get the process-wide active backend, defaulting to [`Git2Backend`] if
+/// [`set_active`] was never called.
+pub fn active() -> &'static dyn GitBackend {
+    ACTIVE.get_or_init(|| Box::new(Git2Backend::new())).as_ref()
+}
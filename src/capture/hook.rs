@@ -0,0 +1,75 @@
+//! Entry points invoked by git hooks and the Claude Code tool-use hook.
+
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::backend;
+
+use super::CaptureHook;
+
+/// Payload Claude Code sends to `ai-blame capture --stdin` after a tool use
+/// that edited a file.
+#[derive(Debug, Deserialize)]
+struct CaptureEvent {
+    file: String,
+    tool: String,
+    #[serde(default)]
+    prompt: String,
+}
+
+/// Record one captured edit from a Claude Code tool-use hook.
+pub fn run_capture_hook() -> Result<()> {
+    let mut input = String::new();
+    std::io::stdin()
+        .read_to_string(&mut input)
+        .context("Failed to read capture hook input")?;
+
+    let event: CaptureEvent =
+        serde_json::from_str(&input).context("Malformed capture hook payload")?;
+
+    let backend = backend::active();
+    let repo_root = backend.discover_workdir(Path::new("."))?;
+    let capture = CaptureHook::new(&repo_root)?;
+
+    let lines = std::fs::read_to_string(&event.file)
+        .map(|content| content.lines().count() as u64)
+        .unwrap_or(0);
+
+    // Relative to the repo root so it can be matched against a commit's
+    // changed files later, regardless of the cwd the hook fired from.
+    let relative_file = Path::new(&event.file)
+        .strip_prefix(&repo_root)
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|_| PathBuf::from(&event.file));
+
+    capture.record(
+        &relative_file.to_string_lossy(),
+        &event.tool,
+        &event.prompt,
+        lines,
+    )
+}
+
+/// Finalize pending capture state into an attribution note.
+///
+/// `commit_oid` names the commit to attach the note to; pass `None` for the
+/// normal post-commit hook case, where it's always the commit that was just
+/// made (`HEAD`). The watch daemon passes the specific historical commit
+/// it's catching up on, so a burst of several commits each get finalized
+/// against their own OID rather than whatever `HEAD` happens to be when the
+/// daemon gets around to them.
+pub fn run_post_commit_hook(commit_oid: Option<&str>) -> Result<()> {
+    let backend = backend::active();
+    let repo_root = backend.discover_workdir(Path::new("."))?;
+    let capture = CaptureHook::new(&repo_root)?;
+
+    let oid = match commit_oid {
+        Some(oid) => oid.to_string(),
+        None => backend.revparse_commit("HEAD")?,
+    };
+
+    capture.finalize(backend, &oid)
+}
@@ -0,0 +1,143 @@
+//! Captures AI-authored edits as they happen (via the `ai-blame capture`
+//! hook Claude Code invokes after each tool use) and finalizes them into an
+//! attribution note through `NotesStore` once a commit exists to attach to.
+
+pub mod hook;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::backend::GitBackend;
+use crate::storage::notes::{Attribution, NotesStore};
+
+/// A single captured AI-authored edit, recorded before the commit that will
+/// contain it exists. `file` is relative to the repo root, so it can later
+/// be matched against a commit's changed files when a burst of several
+/// commits lands between two `finalize` calls.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CapturedEdit {
+    file: String,
+    tool: String,
+    prompt: String,
+    lines: u64,
+}
+
+/// Pending capture state for the working tree, persisted between
+/// `ai-blame capture` invocations until a commit finalizes (or `ai-blame
+/// clear` drops) it.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PendingSession {
+    edits: Vec<CapturedEdit>,
+}
+
+/// Summary of pending capture state, surfaced by `ai-blame status`.
+pub struct CaptureStatus {
+    pub has_pending: bool,
+    pub session_id: Option<String>,
+    pub file_count: usize,
+    pub line_count: u64,
+}
+
+/// Reads/writes pending capture state for a single repo's working tree,
+/// persisted at `.git/ai-blame/pending.json` until a commit finalizes it.
+pub struct CaptureHook {
+    pending_path: PathBuf,
+}
+
+impl CaptureHook {
+    pub fn new(repo_root: &Path) -> Result<Self> {
+        let state_dir = repo_root.join(".git").join("ai-blame");
+        fs::create_dir_all(&state_dir).context("Failed to create ai-blame state directory")?;
+        Ok(Self {
+            pending_path: state_dir.join("pending.json"),
+        })
+    }
+
+    fn load(&self) -> Result<PendingSession> {
+        if !self.pending_path.exists() {
+            return Ok(PendingSession::default());
+        }
+
+        let raw = fs::read_to_string(&self.pending_path)
+            .context("Failed to read pending capture state")?;
+        serde_json::from_str(&raw).context("Malformed pending capture state")
+    }
+
+    fn save(&self, session: &PendingSession) -> Result<()> {
+        let raw = serde_json::to_string_pretty(session)
+            .context("Failed to serialize pending capture state")?;
+        fs::write(&self.pending_path, raw).context("Failed to write pending capture state")
+    }
+
+    /// Record one captured edit (best-effort: `lines` is the file's current
+    /// line count, not a diff against the prior version).
+    pub fn record(&self, file: &str, tool: &str, prompt: &str, lines: u64) -> Result<()> {
+        let mut session = self.load()?;
+        session.edits.push(CapturedEdit {
+            file: file.to_string(),
+            tool: tool.to_string(),
+            prompt: prompt.to_string(),
+            lines,
+        });
+        self.save(&session)
+    }
+
+    pub fn status(&self) -> Result<CaptureStatus> {
+        let session = self.load()?;
+        Ok(CaptureStatus {
+            has_pending: !session.edits.is_empty(),
+            session_id: session.edits.last().map(|edit| edit.tool.clone()),
+            file_count: session.edits.len(),
+            line_count: session.edits.iter().map(|edit| edit.lines).sum(),
+        })
+    }
+
+    pub fn clear_pending(&self) -> Result<()> {
+        self.save(&PendingSession::default())
+    }
+
+    /// Merge the pending edits that touch `commit_oid`'s files into a single
+    /// attribution note on that commit, then drop just those edits from
+    /// pending state. A no-op if none of what's pending matches this commit
+    /// (e.g. a commit the daemon is catching up on that no hook ever
+    /// captured for).
+    ///
+    /// Matching against the commit's own changed files (rather than
+    /// clearing all of pending unconditionally) matters when the watch
+    /// daemon detects a burst of several commits in one poll: without client
+    /// hooks to finalize each commit as it lands, every commit in the burst
+    /// shares the same pending state, and the first one finalized would
+    /// otherwise absorb every edit accumulated since the last poll while the
+    /// rest got none.
+    fn finalize(&self, backend: &dyn GitBackend, commit_oid: &str) -> Result<()> {
+        let mut session = self.load()?;
+        if session.edits.is_empty() {
+            return Ok(());
+        }
+
+        let changed_files = backend.changed_files(commit_oid).unwrap_or_default();
+        let (matched, remaining): (Vec<_>, Vec<_>) = session
+            .edits
+            .into_iter()
+            .partition(|edit| changed_files.iter().any(|f| f == &edit.file));
+        session.edits = remaining;
+
+        if matched.is_empty() {
+            return self.save(&session);
+        }
+
+        let attribution = Attribution {
+            tool: matched.last().map(|edit| edit.tool.clone()).unwrap_or_default(),
+            prompt: matched.last().map(|edit| edit.prompt.clone()).unwrap_or_default(),
+            ai_lines: matched.iter().map(|edit| edit.lines).sum(),
+            human_lines: 0,
+            timestamp: backend.commit_time(commit_oid).unwrap_or(0),
+        };
+
+        NotesStore::new(backend)?.write(commit_oid, &attribution)?;
+        self.save(&session)
+    }
+}
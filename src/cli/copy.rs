@@ -1,9 +1,9 @@
 //! Copy attribution notes between commits
 
-use anyhow::{Context, Result};
+use anyhow::Result;
 use clap::Args;
-use git2::Repository;
 
+use crate::backend;
 use crate::storage::notes::NotesStore;
 
 /// Copy AI attribution from one commit to another
@@ -21,14 +21,14 @@ pub struct CopyNotesArgs {
 }
 
 pub fn run(args: CopyNotesArgs) -> Result<()> {
-    let repo = Repository::discover(".").context("Not in a git repository")?;
+    let backend = backend::active();
 
-    let source_oid = repo.revparse_single(&args.source)?.peel_to_commit()?.id();
-    let target_oid = repo.revparse_single(&args.target)?.peel_to_commit()?.id();
+    let source_oid = backend.revparse_commit(&args.source)?;
+    let target_oid = backend.revparse_commit(&args.target)?;
 
-    let store = NotesStore::new(&repo)?;
+    let store = NotesStore::new(backend)?;
 
-    if !store.has_attribution(source_oid) {
+    if !store.has_attribution(&source_oid) {
         println!("Source commit {} has no attribution.", &args.source);
         return Ok(());
     }
@@ -44,7 +44,7 @@ pub fn run(args: CopyNotesArgs) -> Result<()> {
         return Ok(());
     }
 
-    store.copy_attribution(source_oid, target_oid)?;
+    store.copy_attribution(&source_oid, &target_oid)?;
     println!("Copied attribution: {} -> {}", source_short, target_short);
     Ok(())
 }
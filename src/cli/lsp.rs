@@ -0,0 +1,28 @@
+//! `ai-blame lsp` — start the Language Server Protocol front-end.
+
+use anyhow::Result;
+use clap::Args;
+use tower_lsp::{LspService, Server};
+
+use crate::lsp::Backend;
+
+/// Start an LSP server exposing AI attribution as hovers and code lenses
+#[derive(Debug, Args)]
+pub struct LspArgs {}
+
+pub fn run(_args: LspArgs) -> Result<()> {
+    let runtime = tokio::runtime::Runtime::new()?;
+
+    runtime.block_on(async {
+        let stdin = tokio::io::stdin();
+        let stdout = tokio::io::stdout();
+
+        let (service, socket) = LspService::build(Backend::new)
+            .custom_method("ai-blame/fileSummary", Backend::file_summary)
+            .finish();
+
+        Server::new(stdin, stdout, socket).serve(service).await;
+    });
+
+    Ok(())
+}
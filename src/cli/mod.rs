@@ -1,16 +1,21 @@
 pub mod blame;
+pub mod lsp;
 pub mod output;
 pub mod prompt;
 pub mod show;
 pub mod summary;
+pub mod watch;
 
 use std::fs;
+use std::io::Read;
 use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
 
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
 
 use crate::capture::hook;
+use crate::storage::notes::NotesStore;
 
 /// AI-aware git blame tool for tracking AI-generated code
 #[derive(Debug, Parser)]
@@ -19,6 +24,11 @@ use crate::capture::hook;
 pub struct Cli {
     #[command(subcommand)]
     pub command: Commands,
+
+    /// Which git backend to use: "git2" (default, linked libgit2) or "cli"
+    /// (shell out to the system `git`). Falls back to AI_BLAME_GIT_BACKEND.
+    #[arg(long, global = true)]
+    pub git_backend: Option<crate::backend::GitBackendKind>,
 }
 
 #[derive(Debug, Subcommand)]
@@ -41,6 +51,15 @@ pub enum Commands {
     /// Finalize attribution after a commit (post-commit hook)
     PostCommit,
 
+    /// Migrate attribution across rebases and amends (post-rewrite hook)
+    PostRewrite(PostRewriteArgs),
+
+    /// Run a background daemon that finalizes and syncs attribution
+    Watch(watch::WatchArgs),
+
+    /// Start a Language Server Protocol front-end for attribution
+    Lsp(lsp::LspArgs),
+
     /// Show pending changes status
     Status,
 
@@ -71,10 +90,19 @@ pub struct CaptureArgs {
     pub prompt: Option<String>,
 }
 
+/// Post-rewrite hook arguments
+#[derive(Debug, clap::Args)]
+pub struct PostRewriteArgs {
+    /// Rewrite type git invokes the hook with ("amend" or "rebase")
+    pub rewrite_type: String,
+}
+
 /// Run the CLI
 pub fn run() -> Result<()> {
     let cli = Cli::parse();
 
+    crate::backend::set_active(crate::backend::select(cli.git_backend)?);
+
     match cli.command {
         Commands::Blame(args) => blame::run(args),
         Commands::Prompt(args) => prompt::run(args),
@@ -82,6 +110,9 @@ pub fn run() -> Result<()> {
         Commands::Summary(args) => summary::run(args),
         Commands::Capture(args) => run_capture(args),
         Commands::PostCommit => run_post_commit(),
+        Commands::PostRewrite(args) => run_post_rewrite(args),
+        Commands::Watch(args) => watch::run(args),
+        Commands::Lsp(args) => lsp::run(args),
         Commands::Status => run_status(),
         Commands::Clear => run_clear(),
         Commands::Init => run_init(),
@@ -97,15 +128,58 @@ fn run_capture(args: CaptureArgs) -> Result<()> {
 }
 
 fn run_post_commit() -> Result<()> {
-    hook::run_post_commit_hook()
+    hook::run_post_commit_hook(None)
+}
+
+/// Migrate attribution notes from old commits to their rewritten counterparts.
+///
+/// Git feeds `<old-sha> <new-sha>` pairs on stdin, one per line. Pairs whose
+/// old commit has no attribution are skipped so rebasing un-attributed
+/// history stays a cheap no-op.
+fn run_post_rewrite(args: PostRewriteArgs) -> Result<()> {
+    let _ = args.rewrite_type;
+
+    let mut input = String::new();
+    std::io::stdin()
+        .read_to_string(&mut input)
+        .context("Failed to read post-rewrite input")?;
+
+    let backend = crate::backend::active();
+    let store = NotesStore::new(backend)?;
+
+    for line in input.lines() {
+        let mut parts = line.split_whitespace();
+        let (Some(old_sha), Some(new_sha)) = (parts.next(), parts.next()) else {
+            continue;
+        };
+
+        if !is_commit_oid(old_sha) || !is_commit_oid(new_sha) {
+            continue;
+        }
+
+        if !store.has_attribution(old_sha) {
+            continue;
+        }
+
+        store.copy_attribution(old_sha, new_sha)?;
+    }
+
+    Ok(())
+}
+
+/// Whether `s` looks like a full git object id (40 hex characters).
+///
+/// `post-rewrite` always feeds us full SHAs, so this is just a cheap sanity
+/// check before handing them to the backend.
+fn is_commit_oid(s: &str) -> bool {
+    s.len() == 40 && s.chars().all(|c| c.is_ascii_hexdigit())
 }
 
 fn run_status() -> Result<()> {
-    let repo = git2::Repository::discover(".")?;
-    let repo_root = repo.workdir()
-        .ok_or_else(|| anyhow::anyhow!("No working directory"))?;
+    let backend = crate::backend::active();
+    let repo_root = backend.discover_workdir(Path::new("."))?;
 
-    let hook_handler = crate::capture::CaptureHook::new(repo_root)?;
+    let hook_handler = crate::capture::CaptureHook::new(&repo_root)?;
     let status = hook_handler.status()?;
 
     if status.has_pending {
@@ -122,11 +196,10 @@ fn run_status() -> Result<()> {
 }
 
 fn run_clear() -> Result<()> {
-    let repo = git2::Repository::discover(".")?;
-    let repo_root = repo.workdir()
-        .ok_or_else(|| anyhow::anyhow!("No working directory"))?;
+    let backend = crate::backend::active();
+    let repo_root = backend.discover_workdir(Path::new("."))?;
 
-    let hook_handler = crate::capture::CaptureHook::new(repo_root)?;
+    let hook_handler = crate::capture::CaptureHook::new(&repo_root)?;
     hook_handler.clear_pending()?;
 
     println!("Cleared pending AI attribution.");
@@ -135,35 +208,21 @@ fn run_clear() -> Result<()> {
 }
 
 fn run_init() -> Result<()> {
-    let repo = git2::Repository::discover(".")
+    let backend = crate::backend::active();
+    let repo_root = backend
+        .discover_workdir(Path::new("."))
         .context("Not in a git repository")?;
-    let repo_root = repo.workdir()
-        .ok_or_else(|| anyhow::anyhow!("No working directory"))?;
 
-    // Install post-commit hook
+    // Install hooks
     let hooks_dir = repo_root.join(".git/hooks");
     fs::create_dir_all(&hooks_dir)
         .context("Failed to create hooks directory")?;
 
-    let hook_path = hooks_dir.join("post-commit");
-
-    // Check if hook already exists
-    if hook_path.exists() {
-        let content = fs::read_to_string(&hook_path)?;
-        if content.contains("ai-blame") {
-            println!("✓ ai-blame post-commit hook already installed.");
-        } else {
-            // Append to existing hook
-            let new_content = format!(
-                "{}\n\n# ai-blame post-commit hook\nif command -v ai-blame &> /dev/null; then\n    ai-blame post-commit 2>/dev/null || true\nfi\n",
-                content.trim_end()
-            );
-            fs::write(&hook_path, new_content)?;
-            println!("✓ Added ai-blame to existing post-commit hook.");
-        }
-    } else {
-        // Create new hook
-        let hook_content = r#"#!/bin/bash
+    install_hook(
+        &hooks_dir,
+        "post-commit",
+        "# ai-blame post-commit hook\nif command -v ai-blame &> /dev/null; then\n    ai-blame post-commit 2>/dev/null || true\nfi\n",
+        r#"#!/bin/bash
 # ai-blame post-commit hook
 # Attaches AI attribution notes to the commit
 
@@ -172,19 +231,27 @@ if command -v ai-blame &> /dev/null; then
 elif [[ -x "$HOME/.cargo/bin/ai-blame" ]]; then
     "$HOME/.cargo/bin/ai-blame" post-commit 2>/dev/null || true
 fi
-"#;
-        fs::write(&hook_path, hook_content)?;
+"#,
+    )?;
 
-        // Make executable
-        let mut perms = fs::metadata(&hook_path)?.permissions();
-        perms.set_mode(0o755);
-        fs::set_permissions(&hook_path, perms)?;
+    install_hook(
+        &hooks_dir,
+        "post-rewrite",
+        "# ai-blame post-rewrite hook\nif command -v ai-blame &> /dev/null; then\n    ai-blame post-rewrite \"$1\" 2>/dev/null || true\nfi\n",
+        r#"#!/bin/bash
+# ai-blame post-rewrite hook
+# Migrates AI attribution notes across amended/rebased commits
 
-        println!("✓ Installed ai-blame post-commit hook.");
-    }
+if command -v ai-blame &> /dev/null; then
+    ai-blame post-rewrite "$1" 2>/dev/null || true
+elif [[ -x "$HOME/.cargo/bin/ai-blame" ]]; then
+    "$HOME/.cargo/bin/ai-blame" post-rewrite "$1" 2>/dev/null || true
+fi
+"#,
+    )?;
 
     // Configure git to auto-push/fetch notes with regular push/pull
-    configure_git_notes(&repo)?;
+    configure_git_notes(backend)?;
 
     println!("\nSetup complete! AI attribution will be tracked for commits in this repo.");
     println!("Notes will be automatically pushed/fetched with 'git push' and 'git fetch'.");
@@ -193,25 +260,48 @@ fi
     Ok(())
 }
 
-/// Configure git to automatically push and fetch ai-blame notes
-fn configure_git_notes(repo: &git2::Repository) -> Result<()> {
-    let mut config = repo.config()
-        .context("Failed to open git config")?;
+/// Install (or extend) a git hook script.
+///
+/// If the hook file already exists and isn't managed by ai-blame yet, the
+/// guarded block is appended rather than overwriting whatever the repo
+/// already runs on that hook.
+fn install_hook(hooks_dir: &Path, name: &str, append_block: &str, new_content: &str) -> Result<()> {
+    let hook_path = hooks_dir.join(name);
+
+    if hook_path.exists() {
+        let content = fs::read_to_string(&hook_path)?;
+        if content.contains("ai-blame") {
+            println!("✓ ai-blame {} hook already installed.", name);
+        } else {
+            let new_content = format!("{}\n\n{}", content.trim_end(), append_block);
+            fs::write(&hook_path, new_content)?;
+            println!("✓ Added ai-blame to existing {} hook.", name);
+        }
+    } else {
+        fs::write(&hook_path, new_content)?;
+
+        let mut perms = fs::metadata(&hook_path)?.permissions();
+        perms.set_mode(0o755);
+        fs::set_permissions(&hook_path, perms)?;
 
+        println!("✓ Installed ai-blame {} hook.", name);
+    }
+
+    Ok(())
+}
+
+/// Configure git to automatically push and fetch ai-blame notes
+fn configure_git_notes(backend: &dyn crate::backend::GitBackend) -> Result<()> {
     // Check if push refspec already configured
     let push_refspec = "refs/notes/ai-blame";
-    let push_configured = config
-        .get_string("remote.origin.push")
+    let push_configured = backend
+        .config_get("remote.origin.push")?
         .map(|v| v.contains("ai-blame"))
         .unwrap_or(false);
 
     if !push_configured {
-        // Use multivar to add without replacing existing push configs
-        config.set_multivar("remote.origin.push", "^$", push_refspec)
-            .or_else(|_| {
-                // If multivar fails, try regular set (might be first entry)
-                config.set_str("remote.origin.push", push_refspec)
-            })
+        backend
+            .config_add("remote.origin.push", push_refspec)
             .context("Failed to configure push refspec")?;
         println!("✓ Configured git to push ai-blame notes automatically.");
     } else {
@@ -220,16 +310,14 @@ fn configure_git_notes(repo: &git2::Repository) -> Result<()> {
 
     // Check if fetch refspec already configured
     let fetch_refspec = "+refs/notes/ai-blame:refs/notes/ai-blame";
-    let fetch_configured = config
-        .get_string("remote.origin.fetch")
+    let fetch_configured = backend
+        .config_get("remote.origin.fetch")?
         .map(|v| v.contains("ai-blame"))
         .unwrap_or(false);
 
     if !fetch_configured {
-        config.set_multivar("remote.origin.fetch", "^$", fetch_refspec)
-            .or_else(|_| {
-                config.set_str("remote.origin.fetch", fetch_refspec)
-            })
+        backend
+            .config_add("remote.origin.fetch", fetch_refspec)
             .context("Failed to configure fetch refspec")?;
         println!("✓ Configured git to fetch ai-blame notes automatically.");
     } else {
@@ -238,3 +326,28 @@ fn configure_git_notes(repo: &git2::Repository) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_commit_oid_accepts_full_sha() {
+        assert!(is_commit_oid("a94a8fe5ccb19ba61c4c0873d391e987982fbbd3"));
+    }
+
+    #[test]
+    fn test_is_commit_oid_rejects_short_sha() {
+        assert!(!is_commit_oid("a94a8fe"));
+    }
+
+    #[test]
+    fn test_is_commit_oid_rejects_non_hex() {
+        assert!(!is_commit_oid("zzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzz"));
+    }
+
+    #[test]
+    fn test_is_commit_oid_rejects_empty() {
+        assert!(!is_commit_oid(""));
+    }
+}
@@ -0,0 +1,167 @@
+//! Generate AI-attribution summaries over a range of commits (useful for PRs).
+
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+use clap::Args;
+
+use crate::backend;
+use crate::forge::{self, PrComment, SUMMARY_COMMENT_MARKER};
+use crate::storage::notes::NotesStore;
+
+/// Generate summary for a range of commits (useful for PRs)
+#[derive(Debug, Args)]
+pub struct SummaryArgs {
+    /// Commit range, e.g. `main..feature-branch`
+    pub range: String,
+
+    /// Post the summary as a comment on the given PR/MR number, editing any
+    /// prior ai-blame comment instead of posting a new one.
+    #[arg(long, value_name = "NUMBER")]
+    pub post_pr: Option<u64>,
+}
+
+#[derive(Default)]
+struct RangeStats {
+    ai_lines: u64,
+    human_lines: u64,
+    tools: BTreeMap<String, u64>,
+    prompts: Vec<String>,
+}
+
+pub fn run(args: SummaryArgs) -> Result<()> {
+    let backend = backend::active();
+    let store = NotesStore::new(backend)?;
+
+    let (base, head) = args
+        .range
+        .split_once("..")
+        .context("Range must be in the form <base>..<head>")?;
+
+    let base_oid = backend.revparse_commit(base)?;
+    let head_oid = backend.revparse_commit(head)?;
+
+    let mut stats = RangeStats::default();
+
+    for oid in backend.commits_between(&base_oid, &head_oid)? {
+        if !store.has_attribution(&oid) {
+            continue;
+        }
+
+        let attribution = store.read(&oid)?;
+        stats.ai_lines += attribution.ai_lines;
+        stats.human_lines += attribution.human_lines;
+        *stats.tools.entry(attribution.tool.clone()).or_insert(0) += 1;
+        if !attribution.prompt.trim().is_empty() {
+            stats.prompts.push(attribution.prompt);
+        }
+    }
+
+    let markdown = render_markdown(&args.range, &stats);
+    println!("{}", markdown);
+
+    if let Some(pr_number) = args.post_pr {
+        post_to_pr(pr_number, &markdown)?;
+        println!("\nPosted summary to PR #{}.", pr_number);
+    }
+
+    Ok(())
+}
+
+/// Upsert the rendered summary as a PR comment, choosing the forge
+/// implementation from the `origin` remote URL.
+fn post_to_pr(pr_number: u64, markdown: &str) -> Result<()> {
+    let remote_url = backend::active()
+        .config_get("remote.origin.url")?
+        .context("No 'origin' remote configured")?;
+
+    let forge = forge::from_remote_url(&remote_url)?;
+    forge.upsert_pr_comment(
+        pr_number,
+        &PrComment {
+            marker: SUMMARY_COMMENT_MARKER,
+            body: format!("{}\n{}", SUMMARY_COMMENT_MARKER, markdown),
+        },
+    )
+}
+
+fn render_markdown(range: &str, stats: &RangeStats) -> String {
+    let total_lines = stats.ai_lines + stats.human_lines;
+    let ai_pct = if total_lines == 0 {
+        0.0
+    } else {
+        (stats.ai_lines as f64 / total_lines as f64) * 100.0
+    };
+
+    let mut out = String::new();
+    out.push_str(&format!("## AI attribution summary for `{}`\n\n", range));
+    out.push_str(&format!(
+        "- **{} AI** / **{} human** lines ({:.0}% AI)\n",
+        stats.ai_lines, stats.human_lines, ai_pct
+    ));
+
+    if !stats.tools.is_empty() {
+        let tools: Vec<String> = stats
+            .tools
+            .iter()
+            .map(|(tool, count)| format!("{} ({})", tool, count))
+            .collect();
+        out.push_str(&format!("- Tools used: {}\n", tools.join(", ")));
+    }
+
+    if !stats.prompts.is_empty() {
+        out.push_str("\n<details><summary>Prompts</summary>\n\n");
+        for prompt in &stats.prompts {
+            out.push_str(&format!("- {}\n", prompt.replace('\n', " ")));
+        }
+        out.push_str("\n</details>\n");
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_markdown_empty_range() {
+        let stats = RangeStats::default();
+        let markdown = render_markdown("main..feature", &stats);
+
+        assert!(markdown.contains("AI attribution summary for `main..feature`"));
+        assert!(markdown.contains("0 AI** / **0 human** lines (0% AI)"));
+        assert!(!markdown.contains("Tools used"));
+        assert!(!markdown.contains("Prompts"));
+    }
+
+    #[test]
+    fn test_render_markdown_computes_ai_percentage() {
+        let mut stats = RangeStats::default();
+        stats.ai_lines = 3;
+        stats.human_lines = 1;
+
+        let markdown = render_markdown("main..feature", &stats);
+        assert!(markdown.contains("3 AI** / **1 human** lines (75% AI)"));
+    }
+
+    #[test]
+    fn test_render_markdown_lists_tools() {
+        let mut stats = RangeStats::default();
+        stats.tools.insert("claude".to_string(), 2);
+        stats.tools.insert("copilot".to_string(), 1);
+
+        let markdown = render_markdown("main..feature", &stats);
+        assert!(markdown.contains("Tools used: claude (2), copilot (1)"));
+    }
+
+    #[test]
+    fn test_render_markdown_includes_prompts_section() {
+        let mut stats = RangeStats::default();
+        stats.prompts.push("add retry logic".to_string());
+
+        let markdown = render_markdown("main..feature", &stats);
+        assert!(markdown.contains("<details><summary>Prompts</summary>"));
+        assert!(markdown.contains("- add retry logic"));
+    }
+}
@@ -0,0 +1,43 @@
+//! `ai-blame watch` — background daemon that finalizes and syncs attribution
+//! without relying on client-side hooks.
+
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::Result;
+use clap::Args;
+
+use crate::backend;
+use crate::watch::{self, WatchConfig};
+
+/// Watch the repo for new commits and keep attribution notes finalized and synced
+#[derive(Debug, Args)]
+pub struct WatchArgs {
+    /// How often to poll HEAD for new commits, in seconds
+    #[arg(long, default_value_t = 2)]
+    pub poll_interval_secs: u64,
+
+    /// How often to fetch/push the ai-blame notes ref, in seconds
+    #[arg(long, default_value_t = 60)]
+    pub sync_interval_secs: u64,
+
+    /// Run a single reconcile pass and exit, instead of looping forever
+    #[arg(long)]
+    pub once: bool,
+}
+
+pub fn run(args: WatchArgs) -> Result<()> {
+    // Fail fast if we're not in a git repo at all, before spawning actors.
+    backend::active().discover_workdir(Path::new("."))?;
+
+    let config = WatchConfig {
+        poll_interval: Duration::from_secs(args.poll_interval_secs),
+        sync_interval: Duration::from_secs(args.sync_interval_secs),
+    };
+
+    if args.once {
+        watch::run_once(&config)
+    } else {
+        watch::run(config)
+    }
+}
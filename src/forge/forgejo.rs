@@ -0,0 +1,147 @@
+//! Forgejo/Gitea PR-comment integration, via the REST API.
+
+use anyhow::{bail, Context, Result};
+
+use super::{find_marker_comment_id, split_host_and_path, Forge, PrComment};
+
+/// Forgejo/Gitea REST API client scoped to a single repository.
+pub struct ForgejoForge {
+    base_url: String,
+    owner: String,
+    repo: String,
+    token: String,
+    client: reqwest::blocking::Client,
+}
+
+impl ForgejoForge {
+    pub fn new(base_url: String, owner: String, repo: String, token: String) -> Self {
+        Self {
+            base_url,
+            owner,
+            repo,
+            token,
+            client: reqwest::blocking::Client::new(),
+        }
+    }
+
+    fn api_url(&self, path: &str) -> String {
+        format!(
+            "{}/api/v1/repos/{}/{}{}",
+            self.base_url.trim_end_matches('/'),
+            self.owner,
+            self.repo,
+            path
+        )
+    }
+
+    /// Page size used when listing comments. Forgejo/Gitea default to a
+    /// small page size, which a long-lived PR's comment history can easily
+    /// exceed.
+    const COMMENTS_PER_PAGE: u32 = 50;
+
+    /// Walk every page of the PR's comments looking for one carrying
+    /// `marker`, so an older ai-blame comment further back in a busy PR's
+    /// history is still found (and edited) instead of a duplicate being
+    /// posted on top of it.
+    fn find_existing_comment(&self, pr_number: u64, marker: &str) -> Result<Option<u64>> {
+        find_marker_comment_id(Self::COMMENTS_PER_PAGE, marker, |page| {
+            let response = self
+                .client
+                .get(self.api_url(&format!("/issues/{}/comments", pr_number)))
+                .query(&[
+                    ("limit", Self::COMMENTS_PER_PAGE.to_string()),
+                    ("page", page.to_string()),
+                ])
+                .header("Authorization", format!("token {}", self.token))
+                .send()?;
+
+            if !response.status().is_success() {
+                bail!("Forgejo API returned {}", response.status());
+            }
+
+            Ok(response.json()?)
+        })
+    }
+}
+
+impl Forge for ForgejoForge {
+    fn upsert_pr_comment(&self, pr_number: u64, comment: &PrComment) -> Result<()> {
+        let existing = self.find_existing_comment(pr_number, comment.marker)?;
+        let body = serde_json::json!({ "body": comment.body });
+
+        let response = match existing {
+            Some(comment_id) => self
+                .client
+                .patch(self.api_url(&format!("/issues/comments/{}", comment_id)))
+                .header("Authorization", format!("token {}", self.token))
+                .json(&body)
+                .send()?,
+            None => self
+                .client
+                .post(self.api_url(&format!("/issues/{}/comments", pr_number)))
+                .header("Authorization", format!("token {}", self.token))
+                .json(&body)
+                .send()?,
+        };
+
+        if !response.status().is_success() {
+            bail!("Forgejo API returned {}", response.status());
+        }
+
+        Ok(())
+    }
+}
+
+/// Parse `(base_url, owner, repo)` out of a self-hosted Forgejo/Gitea remote
+/// URL (HTTPS or SSH form).
+pub fn parse_instance(remote_url: &str) -> Result<(String, String, String)> {
+    let (host, path) = split_host_and_path(remote_url)?;
+
+    let (owner, repo) = path
+        .trim_start_matches('/')
+        .split_once('/')
+        .context("Could not parse owner/repo from remote URL")?;
+
+    Ok((
+        format!("https://{}", host),
+        owner.to_string(),
+        repo.to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_instance_https() {
+        let (base_url, owner, repo) =
+            parse_instance("https://forge.example.com/dotsetlabs/ai-blame").unwrap();
+        assert_eq!(base_url, "https://forge.example.com");
+        assert_eq!(owner, "dotsetlabs");
+        assert_eq!(repo, "ai-blame");
+    }
+
+    #[test]
+    fn test_parse_instance_https_with_git_suffix() {
+        let (base_url, owner, repo) =
+            parse_instance("https://forge.example.com/dotsetlabs/ai-blame.git").unwrap();
+        assert_eq!(base_url, "https://forge.example.com");
+        assert_eq!(owner, "dotsetlabs");
+        assert_eq!(repo, "ai-blame");
+    }
+
+    #[test]
+    fn test_parse_instance_ssh() {
+        let (base_url, owner, repo) =
+            parse_instance("git@forge.example.com:dotsetlabs/ai-blame.git").unwrap();
+        assert_eq!(base_url, "https://forge.example.com");
+        assert_eq!(owner, "dotsetlabs");
+        assert_eq!(repo, "ai-blame");
+    }
+
+    #[test]
+    fn test_parse_instance_rejects_malformed_url() {
+        assert!(parse_instance("not-a-url").is_err());
+    }
+}
@@ -0,0 +1,137 @@
+//! GitHub PR-comment integration, via the REST API.
+
+use anyhow::{bail, Result};
+
+use super::{find_marker_comment_id, split_host_and_path, Forge, PrComment};
+
+/// GitHub REST API client scoped to a single repository.
+pub struct GitHubForge {
+    owner: String,
+    repo: String,
+    token: String,
+    client: reqwest::blocking::Client,
+}
+
+impl GitHubForge {
+    pub fn new(owner: String, repo: String, token: String) -> Self {
+        Self {
+            owner,
+            repo,
+            token,
+            client: reqwest::blocking::Client::new(),
+        }
+    }
+
+    fn api_url(&self, path: &str) -> String {
+        format!(
+            "https://api.github.com/repos/{}/{}{}",
+            self.owner, self.repo, path
+        )
+    }
+
+    /// Page size used when listing comments. GitHub defaults to 30 per page,
+    /// which is far smaller than a typical long-lived PR's comment history.
+    const COMMENTS_PER_PAGE: u32 = 100;
+
+    /// Walk every page of the PR's comments looking for one carrying
+    /// `marker`, so an older ai-blame comment further back in a busy PR's
+    /// history is still found (and edited) instead of a duplicate being
+    /// posted on top of it.
+    fn find_existing_comment(&self, pr_number: u64, marker: &str) -> Result<Option<u64>> {
+        find_marker_comment_id(Self::COMMENTS_PER_PAGE, marker, |page| {
+            let response = self
+                .client
+                .get(self.api_url(&format!("/issues/{}/comments", pr_number)))
+                .query(&[
+                    ("per_page", Self::COMMENTS_PER_PAGE.to_string()),
+                    ("page", page.to_string()),
+                ])
+                .bearer_auth(&self.token)
+                .header("User-Agent", "ai-blame")
+                .send()?;
+
+            if !response.status().is_success() {
+                bail!("GitHub API returned {}", response.status());
+            }
+
+            Ok(response.json()?)
+        })
+    }
+}
+
+impl Forge for GitHubForge {
+    fn upsert_pr_comment(&self, pr_number: u64, comment: &PrComment) -> Result<()> {
+        let existing = self.find_existing_comment(pr_number, comment.marker)?;
+        let body = serde_json::json!({ "body": comment.body });
+
+        let response = match existing {
+            Some(comment_id) => self
+                .client
+                .patch(self.api_url(&format!("/issues/comments/{}", comment_id)))
+                .bearer_auth(&self.token)
+                .header("User-Agent", "ai-blame")
+                .json(&body)
+                .send()?,
+            None => self
+                .client
+                .post(self.api_url(&format!("/issues/{}/comments", pr_number)))
+                .bearer_auth(&self.token)
+                .header("User-Agent", "ai-blame")
+                .json(&body)
+                .send()?,
+        };
+
+        if !response.status().is_success() {
+            bail!("GitHub API returned {}", response.status());
+        }
+
+        Ok(())
+    }
+}
+
+/// Parse `owner/repo` out of an `origin` remote URL pointing at github.com
+/// (HTTPS or SSH form).
+pub fn parse_owner_repo(remote_url: &str) -> Result<(String, String)> {
+    let (host, path) = split_host_and_path(remote_url)?;
+    if host != "github.com" {
+        bail!("Not a github.com remote");
+    }
+
+    let (owner, repo) = path
+        .split_once('/')
+        .ok_or_else(|| anyhow::anyhow!("Could not parse owner/repo from remote URL"))?;
+
+    Ok((owner.to_string(), repo.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_owner_repo_https() {
+        let (owner, repo) = parse_owner_repo("https://github.com/dotsetlabs/ai-blame").unwrap();
+        assert_eq!(owner, "dotsetlabs");
+        assert_eq!(repo, "ai-blame");
+    }
+
+    #[test]
+    fn test_parse_owner_repo_https_with_git_suffix() {
+        let (owner, repo) =
+            parse_owner_repo("https://github.com/dotsetlabs/ai-blame.git").unwrap();
+        assert_eq!(owner, "dotsetlabs");
+        assert_eq!(repo, "ai-blame");
+    }
+
+    #[test]
+    fn test_parse_owner_repo_ssh() {
+        let (owner, repo) = parse_owner_repo("git@github.com:dotsetlabs/ai-blame.git").unwrap();
+        assert_eq!(owner, "dotsetlabs");
+        assert_eq!(repo, "ai-blame");
+    }
+
+    #[test]
+    fn test_parse_owner_repo_rejects_other_hosts() {
+        assert!(parse_owner_repo("https://gitlab.com/dotsetlabs/ai-blame").is_err());
+    }
+}
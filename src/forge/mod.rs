@@ -0,0 +1,181 @@
+//! Forge integrations for publishing attribution summaries as PR comments.
+//!
+//! Kept behind the [`Forge`] trait so `summary` only reaches a forge when
+//! `--post-pr` is given (without it, nothing is posted and no network is
+//! needed) and so new forges can be added without touching the
+//! summary-rendering code.
+
+pub mod forgejo;
+pub mod github;
+
+use anyhow::{Context, Result};
+
+/// A single comment body to upsert on a pull request.
+pub struct PrComment {
+    /// Marker embedded in the body so a re-run finds and edits the prior
+    /// comment instead of posting a new one.
+    pub marker: &'static str,
+    pub body: String,
+}
+
+/// Marker embedded in every summary comment ai-blame posts.
+pub const SUMMARY_COMMENT_MARKER: &str = "<!-- ai-blame:summary -->";
+
+/// Minimal surface every forge integration needs to support `summary --post-pr`.
+pub trait Forge {
+    /// Create a comment on the pull request, or edit the existing ai-blame
+    /// comment (identified by [`PrComment::marker`]) if one already exists.
+    fn upsert_pr_comment(&self, pr_number: u64, comment: &PrComment) -> Result<()>;
+}
+
+/// Resolve the `Forge` implementation for a repo's `origin` remote URL.
+///
+/// GitHub remotes (`github.com`) use [`github::GitHubForge`] with a token
+/// from `GITHUB_TOKEN`; anything else is treated as a self-hosted
+/// Forgejo/Gitea instance using [`forgejo::ForgejoForge`] with a token from
+/// `FORGEJO_TOKEN`.
+pub fn from_remote_url(remote_url: &str) -> Result<Box<dyn Forge>> {
+    let (host, _) = split_host_and_path(remote_url)?;
+
+    if host == "github.com" {
+        let token = std::env::var("GITHUB_TOKEN")
+            .context("GITHUB_TOKEN must be set to post PR comments on GitHub")?;
+        let (owner, repo) = github::parse_owner_repo(remote_url)?;
+        return Ok(Box::new(github::GitHubForge::new(owner, repo, token)));
+    }
+
+    let token = std::env::var("FORGEJO_TOKEN")
+        .context("FORGEJO_TOKEN must be set to post PR comments on Forgejo")?;
+    let (base_url, owner, repo) = forgejo::parse_instance(remote_url)?;
+    Ok(Box::new(forgejo::ForgejoForge::new(
+        base_url, owner, repo, token,
+    )))
+}
+
+/// Split a git remote URL (HTTPS or SSH form) into its host and the
+/// repository path after it, e.g. `git@host.example:owner/repo.git` or
+/// `https://host.example/owner/repo` both yield `("host.example",
+/// "owner/repo")`. Used to match the host exactly, rather than checking
+/// whether the whole URL merely contains a known host as a substring (a
+/// self-hosted instance at `mygithub.company.com` contains `github.com` as
+/// a substring but isn't github.com).
+pub(crate) fn split_host_and_path(remote_url: &str) -> Result<(&str, &str)> {
+    let trimmed = remote_url.trim_end_matches(".git");
+
+    if let Some(rest) = trimmed.strip_prefix("git@") {
+        return rest.split_once(':').context("Could not parse SSH remote URL");
+    }
+
+    let without_scheme = trimmed
+        .split_once("://")
+        .map(|(_, rest)| rest)
+        .unwrap_or(trimmed);
+    without_scheme
+        .split_once('/')
+        .context("Could not parse remote URL")
+}
+
+/// Page size used when listing PR comments, and a function fetching one
+/// page by number.
+///
+/// Walks pages (1-indexed) looking for a comment whose body contains
+/// `marker`, stopping as soon as it's found or a page comes back shorter
+/// than `page_size` (or empty). Shared by every forge so the pagination
+/// logic — easy to get subtly wrong — is written once.
+pub(crate) fn find_marker_comment_id<F>(
+    page_size: u32,
+    marker: &str,
+    fetch_page: F,
+) -> Result<Option<u64>>
+where
+    F: Fn(u32) -> Result<Vec<serde_json::Value>>,
+{
+    for page in 1.. {
+        let comments = fetch_page(page)?;
+        if comments.is_empty() {
+            return Ok(None);
+        }
+
+        let page_len = comments.len() as u32;
+        if let Some(id) = comments.into_iter().find_map(|c| {
+            let body = c.get("body")?.as_str()?;
+            if body.contains(marker) {
+                c.get("id")?.as_u64()
+            } else {
+                None
+            }
+        }) {
+            return Ok(Some(id));
+        }
+
+        if page_len < page_size {
+            return Ok(None);
+        }
+    }
+
+    unreachable!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn comment(id: u64, body: &str) -> serde_json::Value {
+        serde_json::json!({ "id": id, "body": body })
+    }
+
+    #[test]
+    fn test_find_marker_comment_id_finds_on_first_page() {
+        let pages = [vec![
+            comment(1, "hello"),
+            comment(2, "<!-- ai-blame:summary -->"),
+        ]];
+
+        let found = find_marker_comment_id(2, "<!-- ai-blame:summary -->", |page| {
+            Ok(pages.get(page as usize - 1).cloned().unwrap_or_default())
+        })
+        .unwrap();
+
+        assert_eq!(found, Some(2));
+    }
+
+    #[test]
+    fn test_find_marker_comment_id_finds_on_later_page() {
+        let pages = [
+            vec![comment(1, "hello"), comment(2, "world")],
+            vec![comment(3, "<!-- ai-blame:summary -->")],
+        ];
+
+        let found = find_marker_comment_id(2, "<!-- ai-blame:summary -->", |page| {
+            Ok(pages.get(page as usize - 1).cloned().unwrap_or_default())
+        })
+        .unwrap();
+
+        assert_eq!(found, Some(3));
+    }
+
+    #[test]
+    fn test_find_marker_comment_id_stops_at_short_page() {
+        let pages = [vec![comment(1, "hello")]];
+
+        let found = find_marker_comment_id(2, "<!-- ai-blame:summary -->", |page| {
+            Ok(pages.get(page as usize - 1).cloned().unwrap_or_default())
+        })
+        .unwrap();
+
+        assert_eq!(found, None);
+    }
+
+    #[test]
+    fn test_find_marker_comment_id_not_found_returns_none_on_empty_page() {
+        let pages: [Vec<serde_json::Value>; 2] =
+            [vec![comment(1, "hello"), comment(2, "world")], Vec::new()];
+
+        let found = find_marker_comment_id(2, "<!-- ai-blame:summary -->", |page| {
+            Ok(pages.get(page as usize - 1).cloned().unwrap_or_default())
+        })
+        .unwrap();
+
+        assert_eq!(found, None);
+    }
+}
@@ -0,0 +1,236 @@
+//! `ai-blame lsp` — a Language Server Protocol front-end for attribution.
+//!
+//! Exposes the same data `blame::run` and `prompt::run` compute over the
+//! CLI as hovers and code lenses in an editor: hovering a line surfaces the
+//! tool, prompt and timestamp that produced it, and lenses mark each
+//! AI-authored hunk. A custom `ai-blame/fileSummary` request mirrors
+//! `show`/`summary` for a whole buffer.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use tower_lsp::jsonrpc::{Error as RpcError, Result as RpcResult};
+use tower_lsp::lsp_types::*;
+use tower_lsp::{Client, LanguageServer};
+
+use crate::storage::notes::NotesStore;
+
+/// Per-line attribution surfaced as a hover or code lens.
+struct LineAttribution {
+    tool: String,
+    prompt: String,
+    timestamp: i64,
+    is_ai: bool,
+}
+
+/// Language server state. There's no cached workspace root: each request
+/// anchors its own `NotesStore` to the requested file's discovered repo
+/// (see `file_attributions`), since a long-running server's cwd isn't
+/// guaranteed to be the workspace root the way a CLI invocation's is.
+pub struct Backend {
+    client: Client,
+}
+
+/// Response payload for the custom `ai-blame/fileSummary` request.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FileSummaryResult {
+    pub ai_lines: u64,
+    pub human_lines: u64,
+    pub tools: Vec<String>,
+}
+
+impl Backend {
+    pub fn new(client: Client) -> Self {
+        Self { client }
+    }
+
+    /// Resolve `path` against its own discovered repo and read per-line
+    /// attribution through `NotesStore`, via the active `GitBackend` — the
+    /// same source `blame::run` uses. Anchored to `path` rather than the
+    /// process's cwd, since the LSP server commonly runs from a directory
+    /// that isn't the workspace root.
+    fn file_attributions(&self, path: &Path) -> anyhow::Result<Vec<LineAttribution>> {
+        let backend = crate::backend::active();
+        let workdir = backend.discover_workdir(path.parent().unwrap_or(path))?;
+        let store = NotesStore::new_at(backend, workdir)?;
+
+        let mut lines = Vec::new();
+        for oid in backend.blame_lines(path)? {
+            let attribution = if store.has_attribution(&oid) {
+                Some(store.read(&oid)?)
+            } else {
+                None
+            };
+
+            lines.push(match attribution {
+                Some(note) => LineAttribution {
+                    tool: note.tool,
+                    prompt: note.prompt,
+                    timestamp: note.timestamp,
+                    is_ai: true,
+                },
+                None => LineAttribution {
+                    tool: String::new(),
+                    prompt: String::new(),
+                    timestamp: 0,
+                    is_ai: false,
+                },
+            });
+        }
+
+        Ok(lines)
+    }
+
+    /// Handle the custom `ai-blame/fileSummary` request: the same
+    /// aggregate `show`/`summary` compute for a commit, but for a buffer.
+    pub async fn file_summary(&self, doc: TextDocumentIdentifier) -> RpcResult<FileSummaryResult> {
+        let path = doc
+            .uri
+            .to_file_path()
+            .map_err(|_| RpcError::invalid_params("not a file:// URI"))?;
+
+        let lines = self
+            .file_attributions(&path)
+            .map_err(|e| RpcError::invalid_params(e.to_string()))?;
+
+        let ai_lines = lines.iter().filter(|l| l.is_ai).count() as u64;
+        let human_lines = lines.len() as u64 - ai_lines;
+
+        let mut tools: Vec<String> = lines
+            .iter()
+            .filter(|l| l.is_ai)
+            .map(|l| l.tool.clone())
+            .collect();
+        tools.sort();
+        tools.dedup();
+
+        Ok(FileSummaryResult {
+            ai_lines,
+            human_lines,
+            tools,
+        })
+    }
+}
+
+#[tower_lsp::async_trait]
+impl LanguageServer for Backend {
+    async fn initialize(&self, _params: InitializeParams) -> RpcResult<InitializeResult> {
+        Ok(InitializeResult {
+            capabilities: ServerCapabilities {
+                hover_provider: Some(HoverProviderCapability::Simple(true)),
+                code_lens_provider: Some(CodeLensOptions {
+                    resolve_provider: Some(false),
+                }),
+                text_document_sync: Some(TextDocumentSyncCapability::Kind(
+                    TextDocumentSyncKind::FULL,
+                )),
+                ..ServerCapabilities::default()
+            },
+            ..InitializeResult::default()
+        })
+    }
+
+    async fn initialized(&self, _: InitializedParams) {
+        self.client
+            .log_message(MessageType::INFO, "ai-blame lsp ready")
+            .await;
+    }
+
+    async fn shutdown(&self) -> RpcResult<()> {
+        Ok(())
+    }
+
+    async fn did_save(&self, _params: DidSaveTextDocumentParams) {
+        // Attribution is read fresh from `NotesStore` on every request (it
+        // only changes after the post-commit/post-rewrite hooks fire), so
+        // there's no cache to invalidate here beyond this log line.
+        self.client
+            .log_message(MessageType::INFO, "ai-blame: refreshing attribution")
+            .await;
+    }
+
+    async fn hover(&self, params: HoverParams) -> RpcResult<Option<Hover>> {
+        let uri = params.text_document_position_params.text_document.uri;
+        let line = params.text_document_position_params.position.line as usize;
+
+        let Ok(path) = uri.to_file_path() else {
+            return Ok(None);
+        };
+
+        let lines = self
+            .file_attributions(&path)
+            .map_err(|e| RpcError::invalid_params(e.to_string()))?;
+
+        let Some(attribution) = lines.get(line) else {
+            return Ok(None);
+        };
+
+        if !attribution.is_ai {
+            return Ok(None);
+        }
+
+        Ok(Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: format!(
+                    "**AI-generated** via `{}` (committed {})\n\n> {}",
+                    attribution.tool,
+                    format_commit_timestamp(attribution.timestamp),
+                    attribution.prompt
+                ),
+            }),
+            range: None,
+        }))
+    }
+
+    async fn code_lens(&self, params: CodeLensParams) -> RpcResult<Option<Vec<CodeLens>>> {
+        let Ok(path) = params.text_document.uri.to_file_path() else {
+            return Ok(None);
+        };
+
+        let lines = self
+            .file_attributions(&path)
+            .map_err(|e| RpcError::invalid_params(e.to_string()))?;
+
+        Ok(Some(ai_hunks_as_lenses(&lines)))
+    }
+}
+
+/// Collapse consecutive AI-attributed lines from the same tool into a single
+/// code lens per hunk, mirroring the hunks `blame::run` prints.
+fn ai_hunks_as_lenses(lines: &[LineAttribution]) -> Vec<CodeLens> {
+    let mut lenses = Vec::new();
+    let mut hunk_start = 0usize;
+
+    for i in 1..=lines.len() {
+        let hunk_ended = i == lines.len() || lines[i].is_ai != lines[hunk_start].is_ai || lines[i].tool != lines[hunk_start].tool;
+
+        if hunk_ended {
+            let hunk = &lines[hunk_start];
+            if hunk.is_ai {
+                lenses.push(CodeLens {
+                    range: Range {
+                        start: Position::new(hunk_start as u32, 0),
+                        end: Position::new(i as u32, 0),
+                    },
+                    command: Some(Command {
+                        title: format!("AI ({})", hunk.tool),
+                        command: "ai-blame.showPrompt".to_string(),
+                        arguments: None,
+                    }),
+                    data: None,
+                });
+            }
+            hunk_start = i;
+        }
+    }
+
+    lenses
+}
+
+/// Render a commit's committer time (Unix timestamp, seconds since the epoch)
+/// for display in a hover. Kept dependency-free rather than pulling in a
+/// calendar-formatting crate just for this.
+fn format_commit_timestamp(timestamp: i64) -> String {
+    format!("{} (unix time)", timestamp)
+}
@@ -0,0 +1,108 @@
+//! Storage for AI-attribution notes under `refs/notes/ai-blame`.
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+
+use crate::backend::GitBackend;
+
+/// A single commit's AI attribution, as stored in its `refs/notes/ai-blame` note.
+#[derive(Debug, Clone)]
+pub struct Attribution {
+    pub tool: String,
+    pub prompt: String,
+    pub ai_lines: u64,
+    pub human_lines: u64,
+    /// The commit's committer time, as a Unix timestamp.
+    pub timestamp: i64,
+}
+
+/// Reads and writes AI-attribution notes through a [`GitBackend`], so
+/// callers don't need to care whether notes are read via libgit2 or the
+/// system `git` CLI.
+pub struct NotesStore<'a> {
+    backend: &'a dyn GitBackend,
+    anchor: PathBuf,
+}
+
+impl<'a> NotesStore<'a> {
+    /// Construct a store that resolves the repo from the process's current
+    /// directory, the same way the backend's other operations do. Correct
+    /// for every CLI command and the watch daemon, which always run with
+    /// their cwd inside the repo.
+    pub fn new(backend: &'a dyn GitBackend) -> Result<Self> {
+        Ok(Self {
+            backend,
+            anchor: PathBuf::from("."),
+        })
+    }
+
+    /// Construct a store anchored to `anchor` instead of the process's
+    /// current directory — for long-running callers (the LSP server) whose
+    /// cwd isn't guaranteed to be the workspace root.
+    pub fn new_at(backend: &'a dyn GitBackend, anchor: PathBuf) -> Result<Self> {
+        Ok(Self { backend, anchor })
+    }
+
+    /// Whether `commit_oid` has an attribution note attached.
+    pub fn has_attribution(&self, commit_oid: &str) -> bool {
+        matches!(
+            self.backend.read_note(&self.anchor, commit_oid),
+            Ok(Some(_))
+        )
+    }
+
+    /// Read and parse the attribution note attached to `commit_oid`.
+    pub fn read(&self, commit_oid: &str) -> Result<Attribution> {
+        let note = self
+            .backend
+            .read_note(&self.anchor, commit_oid)?
+            .context("no attribution note for commit")?;
+        parse_attribution(&note)
+    }
+
+    /// Copy the attribution note from `source_oid` to `target_oid`.
+    pub fn copy_attribution(&self, source_oid: &str, target_oid: &str) -> Result<()> {
+        self.backend.copy_note(source_oid, target_oid)?;
+        Ok(())
+    }
+
+    /// Write (replacing any existing) attribution note on `commit_oid`.
+    pub fn write(&self, commit_oid: &str, attribution: &Attribution) -> Result<()> {
+        let note = serde_json::json!({
+            "tool": attribution.tool,
+            "prompt": attribution.prompt,
+            "ai_lines": attribution.ai_lines,
+            "human_lines": attribution.human_lines,
+            "timestamp": attribution.timestamp,
+        })
+        .to_string();
+
+        self.backend.write_note(commit_oid, &note)?;
+        Ok(())
+    }
+}
+
+fn parse_attribution(note: &str) -> Result<Attribution> {
+    let value: serde_json::Value =
+        serde_json::from_str(note).context("malformed attribution note")?;
+
+    Ok(Attribution {
+        tool: value
+            .get("tool")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string(),
+        prompt: value
+            .get("prompt")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string(),
+        ai_lines: value.get("ai_lines").and_then(|v| v.as_u64()).unwrap_or(0),
+        human_lines: value
+            .get("human_lines")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0),
+        timestamp: value.get("timestamp").and_then(|v| v.as_i64()).unwrap_or(0),
+    })
+}
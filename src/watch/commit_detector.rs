@@ -0,0 +1,74 @@
+//! Actor that watches the repo for new commits.
+//!
+//! Polls `HEAD` on its own interval and, when it moves, walks every commit
+//! between the previous and current `HEAD` — not just the latest one — so a
+//! burst of several commits landing within one `poll_interval` (a scripted
+//! sequence, a fast series of commits) still gets each commit reported,
+//! rather than silently collapsing to whichever commit `HEAD` happens to be
+//! sitting on when the daemon next wakes up.
+
+use std::sync::mpsc::Sender;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use anyhow::Result;
+
+use crate::backend;
+
+use super::CommitDetected;
+
+/// Spawn the commit-detector actor, polling `HEAD` every `interval` and
+/// sending a [`CommitDetected`] message on `tx` listing every commit since
+/// the previous poll, oldest first, whenever `HEAD` has moved.
+pub fn spawn(interval: Duration, tx: Sender<CommitDetected>) -> JoinHandle<()> {
+    thread::spawn(move || {
+        let mut last_head = current_head().ok();
+
+        loop {
+            thread::sleep(interval);
+
+            let head = match current_head() {
+                Ok(head) => head,
+                Err(_) => continue,
+            };
+
+            if last_head.as_ref() == Some(&head) {
+                continue;
+            }
+
+            let commits = match &last_head {
+                Some(previous) => backend::active().commits_between(previous, &head),
+                // No prior baseline (the daemon started before any commit
+                // existed): there's nothing to range over, so treat the new
+                // HEAD as the only commit to report.
+                None => Ok(vec![head.clone()]),
+            };
+
+            last_head = Some(head);
+
+            match commits {
+                Ok(commits) if !commits.is_empty() => {
+                    if tx.send(CommitDetected { commits }).is_err() {
+                        return;
+                    }
+                }
+                Ok(_) => {}
+                Err(_) => continue,
+            }
+        }
+    })
+}
+
+/// Check once for commits to finalize. Used by `--once` reconcile passes,
+/// which have no prior state to diff against so treat any existing `HEAD` as
+/// a commit to finalize.
+pub fn poll_once() -> Result<Vec<String>> {
+    match current_head() {
+        Ok(head) => Ok(vec![head]),
+        Err(_) => Ok(Vec::new()),
+    }
+}
+
+fn current_head() -> Result<String> {
+    Ok(backend::active().revparse_commit("HEAD")?)
+}
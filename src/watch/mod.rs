@@ -0,0 +1,69 @@
+//! Background daemon that keeps AI-attribution notes finalized and in sync
+//! across clones without relying on client-side hooks.
+//!
+//! Structured as two independent actors communicating by message: a
+//! [`commit_detector`] actor watches the repo for new commits, and a
+//! [`notes_sync`] actor periodically fetches/pushes `refs/notes/ai-blame`.
+//! Keeping detection and sync as separate actors lets their cadences be
+//! configured independently of one another.
+
+pub mod commit_detector;
+pub mod notes_sync;
+
+use std::sync::mpsc;
+use std::time::Duration;
+
+use anyhow::Result;
+
+/// Message sent by the commit-detector actor when `HEAD` has moved, listing
+/// every commit since the previous poll (oldest first) so a burst of
+/// several commits in one interval doesn't collapse to just the latest.
+pub struct CommitDetected {
+    pub commits: Vec<String>,
+}
+
+/// Configuration for a watch run. There's no repo-root field: the actors
+/// resolve the repo through the active `GitBackend`, which works from the
+/// process's current directory the same way every other command does, so
+/// the CLI entry point only needs to validate up front that we're in a
+/// repo at all (see `cli::watch::run`).
+pub struct WatchConfig {
+    pub poll_interval: Duration,
+    pub sync_interval: Duration,
+}
+
+/// Run the watch daemon's actors until interrupted.
+pub fn run(config: WatchConfig) -> Result<()> {
+    let (tx, rx) = mpsc::channel();
+
+    let detector = commit_detector::spawn(config.poll_interval, tx);
+    let sync = notes_sync::spawn(config.sync_interval);
+
+    for detected in rx {
+        for commit in detected.commits {
+            if let Err(err) = crate::capture::hook::run_post_commit_hook(Some(&commit)) {
+                eprintln!(
+                    "ai-blame watch: failed to finalize commit {}: {}",
+                    commit, err
+                );
+            }
+        }
+    }
+
+    detector.join().ok();
+    sync.join().ok();
+
+    Ok(())
+}
+
+/// Run a single reconcile pass: finalize a newly detected commit (if any)
+/// and sync notes once, then return. Backs `ai-blame watch --once` for CI.
+pub fn run_once(_config: &WatchConfig) -> Result<()> {
+    for commit in commit_detector::poll_once()? {
+        crate::capture::hook::run_post_commit_hook(Some(&commit))?;
+    }
+
+    notes_sync::sync_once()?;
+
+    Ok(())
+}
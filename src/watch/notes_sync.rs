@@ -0,0 +1,32 @@
+//! Actor that keeps `refs/notes/ai-blame` converged across clones.
+//!
+//! Fetches then pushes the notes ref on its own interval so attribution
+//! created on one machine shows up on another without anyone running
+//! `git fetch`/`git push` by hand.
+
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use anyhow::Result;
+
+use crate::backend;
+
+/// Spawn the notes-sync actor, fetching and pushing the notes ref every
+/// `interval`.
+pub fn spawn(interval: Duration) -> JoinHandle<()> {
+    thread::spawn(move || loop {
+        if let Err(err) = sync_once() {
+            eprintln!("ai-blame watch: notes sync failed: {}", err);
+        }
+        thread::sleep(interval);
+    })
+}
+
+/// Fetch then push `refs/notes/ai-blame` against `origin` once, through the
+/// active [`backend::GitBackend`].
+pub fn sync_once() -> Result<()> {
+    let backend = backend::active();
+    backend.fetch_notes()?;
+    backend.push_notes()?;
+    Ok(())
+}